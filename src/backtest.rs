@@ -0,0 +1,32 @@
+//! Fork-based backtesting: run the live bot's exact quoter-path logic against
+//! a deterministic, pinned Anvil fork of Polygon instead of the live chain.
+//!
+//! `--backtest <block>` launches `anvil --fork-url <POLYGON_RPC_URL>
+//! --fork-block-number <block>` and points the provider at its local
+//! endpoint. Anvil's endpoint is plain JSON-RPC over HTTP, so `main`'s loop
+//! needs no branching between live and forked runs — only the RPC URL
+//! differs, which is resolved once here before the loop starts.
+
+use anyhow::{Context, Result};
+use ethers::utils::{Anvil, AnvilInstance};
+
+/// `--backtest <block>` parsed from `argv`, if present.
+pub fn requested_fork_block(args: &[String]) -> Result<Option<u64>> {
+    let Some(pos) = args.iter().position(|a| a == "--backtest") else {
+        return Ok(None);
+    };
+    let block_str = args.get(pos + 1).context("--backtest requires a block number argument")?;
+    let block = block_str.parse::<u64>().with_context(|| format!("invalid --backtest block {block_str:?}"))?;
+    Ok(Some(block))
+}
+
+/// Spawns an Anvil fork of `fork_url` pinned at `fork_block_number`. The
+/// returned [`AnvilInstance`] must stay alive for the duration of the run —
+/// dropping it kills the child process and the local RPC endpoint with it.
+///
+/// `AnvilBuilder::spawn` has no fallible variant — it panics internally if
+/// `anvil` isn't on `PATH` or fails to report its endpoint — so there's
+/// nothing here to wrap in `Context`.
+pub fn spawn_fork(fork_url: &str, fork_block_number: u64) -> Result<AnvilInstance> {
+    Ok(Anvil::new().fork(fork_url).fork_block_number(fork_block_number).spawn())
+}
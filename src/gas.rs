@@ -0,0 +1,104 @@
+//! EIP-1559 gas costing for the round-trip arb swap.
+//!
+//! Polygon blocks carry a `base_fee_per_gas` that moves with demand, so a flat
+//! `GAS_USDC_PER_TX` guess drifts badly during gas spikes. This module pulls
+//! the real effective gas price (base fee + priority tip) and combines it with
+//! the per-leg gas units to get a wei cost, which the caller then converts to
+//! USDC by quoting WMATIC->USDC.
+
+use anyhow::{anyhow, Context, Result};
+use ethers::prelude::*;
+use std::sync::Arc;
+
+/// Gas units burned by an Algebra (QuickSwap) leg. `quoteExactInputSingle` on
+/// the Algebra quoter returns no `gasEstimate`, unlike Uniswap's QuoterV2, so
+/// we fall back to this configured constant.
+pub const ALGEBRA_GAS_UNITS_DEFAULT: u64 = 180_000;
+
+/// Gas units burned by a Uniswap v3 leg. The live `QuoterV2` call used to hand
+/// back a real `gasEstimate`, but the round-trip loop now quotes locally
+/// against a cached [`crate::pool_sim::PoolState`] (no RPC, no gas estimate),
+/// so it uses this configured constant too.
+pub const UNISWAP_GAS_UNITS_DEFAULT: u64 = 130_000;
+
+/// Gas units burned by a Curve-style StableSwap leg. `compute_y` is evaluated
+/// locally (no on-chain call, no `gasEstimate`), so this is a configured
+/// constant like [`ALGEBRA_GAS_UNITS_DEFAULT`].
+pub const STABLESWAP_GAS_UNITS_DEFAULT: u64 = 150_000;
+
+/// Fallback priority tip (30 gwei) used only if both `eth_maxPriorityFeePerGas`
+/// and `eth_feeHistory` are unavailable.
+const PRIORITY_FEE_FALLBACK_WEI: u64 = 30_000_000_000;
+
+/// `base_fee_per_gas` of the pending block plus a priority tip, i.e. the price
+/// per gas unit a transaction landing in the next block should expect to pay.
+pub async fn effective_gas_price<M>(provider: &Arc<M>) -> Result<U256>
+where
+    M: Middleware,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let pending = provider
+        .get_block(BlockNumber::Pending)
+        .await
+        .context("fetching pending block")?
+        .ok_or_else(|| anyhow!("no pending block returned"))?;
+    let base_fee = pending
+        .base_fee_per_gas
+        .ok_or_else(|| anyhow!("pending block missing base_fee_per_gas (not EIP-1559?)"))?;
+    let tip = priority_fee(provider).await?;
+    Ok(base_fee + tip)
+}
+
+async fn priority_fee<M>(provider: &Arc<M>) -> Result<U256>
+where
+    M: Middleware,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    match provider
+        .provider()
+        .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+        .await
+    {
+        Ok(tip) => Ok(tip),
+        Err(_) => fee_history_tip(provider).await,
+    }
+}
+
+/// Median of the 50th-percentile priority fee over the last 10 blocks, used
+/// when the node doesn't implement `eth_maxPriorityFeePerGas`.
+async fn fee_history_tip<M>(provider: &Arc<M>) -> Result<U256>
+where
+    M: Middleware,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let history = provider
+        .fee_history(10u64, BlockNumber::Latest, &[50.0])
+        .await
+        .context("eth_feeHistory")?;
+
+    let tips: Vec<U256> = history.reward.into_iter().filter_map(|r| r.first().copied()).collect();
+    if tips.is_empty() {
+        return Ok(U256::from(PRIORITY_FEE_FALLBACK_WEI));
+    }
+    let sum = tips.iter().fold(U256::zero(), |acc, t| acc + *t);
+    Ok(sum / U256::from(tips.len() as u64))
+}
+
+/// Gas units for the two legs of a round trip: whichever one routed through
+/// Uniswap's QuoterV2 carries a real `gasEstimate`, the Algebra leg uses the
+/// configured default.
+pub struct RoundTripGasUnits {
+    pub uni_leg: U256,
+    pub algebra_leg: U256,
+}
+
+impl RoundTripGasUnits {
+    pub fn total(&self) -> U256 {
+        self.uni_leg + self.algebra_leg
+    }
+}
+
+/// Wei cost of the round trip at the given effective gas price.
+pub fn round_trip_wei(units: &RoundTripGasUnits, effective_gas_price: U256) -> U256 {
+    units.total() * effective_gas_price
+}
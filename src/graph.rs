@@ -0,0 +1,248 @@
+//! Generalized negative-cycle arbitrage search over a token/pool graph.
+//!
+//! PATH A and PATH B are hardcoded for WETH/USDC across two DEXes; adding a
+//! third token or venue means rewriting `main`. This module instead builds a
+//! directed graph whose nodes are tokens and whose edges are `(pool,
+//! direction)` pairs weighted by `-ln(rate * (1 - fee))`, so any
+//! gross-profitable loop through any number of tokens/venues shows up as a
+//! negative cycle. Tokens and pools come from a config file so users can scan
+//! arbitrary multi-hop cycles instead of a fixed pair.
+
+use anyhow::{bail, Context, Result};
+use ethers::types::{Address, U256};
+use serde::Deserialize;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+
+/// Which quoter backs a pool's edges.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Venue {
+    UniswapV3,
+    Algebra,
+    StableSwap,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolConfig {
+    pub pool: Address,
+    pub venue: Venue,
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Fee in parts-per-million (e.g. `3000` = 0.3%), used only to weight
+    /// the edge; the real fee deduction happens in the quoter itself.
+    pub fee_ppm: u32,
+    /// `venue: StableSwap` only: `token_in`/`token_out`'s coin indices and the
+    /// pool's total coin count, needed to call its `compute_y` quote. Ignored
+    /// for Uniswap/Algebra pools, which quote by token address instead.
+    #[serde(default)]
+    pub coin_in_index: Option<usize>,
+    #[serde(default)]
+    pub coin_out_index: Option<usize>,
+    #[serde(default)]
+    pub n_coins: Option<usize>,
+}
+
+/// Tokens and pools to scan, loaded from a JSON file so the graph isn't
+/// hardcoded to one pair.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphConfig {
+    pub tokens: Vec<Address>,
+    pub pools: Vec<PoolConfig>,
+}
+
+impl GraphConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let text = fs::read_to_string(&path).with_context(|| format!("reading graph config {:?}", path.as_ref()))?;
+        serde_json::from_str(&text).context("parsing graph config")
+    }
+}
+
+/// `--graph <config.json>` parsed from `argv`, if present.
+pub fn requested_config_path(args: &[String]) -> Option<&str> {
+    let pos = args.iter().position(|a| a == "--graph")?;
+    args.get(pos + 1).map(String::as_str)
+}
+
+/// A directed edge: trade `token_in` -> `token_out` through `pools[pool_index]`.
+#[derive(Debug, Clone, Copy)]
+pub struct Edge {
+    pub pool_index: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: f64,
+}
+
+pub struct Graph {
+    pub node_count: usize,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Builds the graph from `config`, given each pool's currently quoted
+    /// exchange rate (`rates[i]` = units of `token_out` per unit of
+    /// `token_in` for `config.pools[i]`). A pool whose rate is non-positive
+    /// (a failed quote, or one this round's dust probe couldn't price) is
+    /// skipped rather than failing the whole build, so one bad pool doesn't
+    /// blank out the scan for every other edge.
+    pub fn build(config: &GraphConfig, rates: &[f64]) -> Result<Self> {
+        if config.pools.len() != rates.len() {
+            bail!("pools/rates length mismatch: {} pools, {} rates", config.pools.len(), rates.len());
+        }
+        let mut edges = Vec::with_capacity(config.pools.len());
+        for (i, pool) in config.pools.iter().enumerate() {
+            let fee = pool.fee_ppm as f64 / 1_000_000.0;
+            let effective_rate = rates[i] * (1.0 - fee);
+            if effective_rate <= 0.0 {
+                continue;
+            }
+            let from = config.tokens.iter().position(|t| *t == pool.token_in).context("token_in not in token list")?;
+            let to = config.tokens.iter().position(|t| *t == pool.token_out).context("token_out not in token list")?;
+            edges.push(Edge { pool_index: i, from, to, weight: -effective_rate.ln() });
+        }
+        Ok(Self { node_count: config.tokens.len(), edges })
+    }
+
+    /// Runs Bellman-Ford from `source` for `|V|-1` relaxation rounds, then one
+    /// extra round to detect a negative cycle. Returns the cycle reconstructed
+    /// from predecessor pointers, alongside the specific edge (pool) used to
+    /// reach each node — tracked separately from the node path because two
+    /// pools can connect the same token pair, so the edge can't be re-derived
+    /// from endpoints alone (see [`requote_cycle`]).
+    pub fn find_negative_cycle(&self, source: usize) -> Option<Cycle> {
+        let n = self.node_count;
+        let mut dist = vec![f64::INFINITY; n];
+        let mut pred: Vec<Option<usize>> = vec![None; n];
+        let mut pred_edge: Vec<Option<usize>> = vec![None; n];
+        dist[source] = 0.0;
+
+        for _ in 0..n.saturating_sub(1) {
+            for (edge_index, edge) in self.edges.iter().enumerate() {
+                if dist[edge.from].is_finite() && dist[edge.from] + edge.weight < dist[edge.to] {
+                    dist[edge.to] = dist[edge.from] + edge.weight;
+                    pred[edge.to] = Some(edge.from);
+                    pred_edge[edge.to] = Some(edge_index);
+                }
+            }
+        }
+
+        // Extra relaxation round: any node that still improves sits on (or
+        // downstream of) a negative cycle.
+        let mut cycle_node = None;
+        for (edge_index, edge) in self.edges.iter().enumerate() {
+            if dist[edge.from].is_finite() && dist[edge.from] + edge.weight < dist[edge.to] {
+                pred[edge.to] = Some(edge.from);
+                pred_edge[edge.to] = Some(edge_index);
+                cycle_node = Some(edge.to);
+                break;
+            }
+        }
+        let mut node = cycle_node?;
+
+        // Walk back |V| steps to land inside the cycle itself, not just a
+        // node reachable from it.
+        for _ in 0..n {
+            node = pred[node]?;
+        }
+
+        let mut nodes = vec![node];
+        let mut cur = pred[node]?;
+        while cur != node {
+            nodes.push(cur);
+            cur = pred[cur]?;
+        }
+        nodes.push(node);
+        nodes.reverse();
+
+        // `nodes[i+1]` was reached via `pred_edge[nodes[i+1]]`.
+        let edges = nodes.windows(2).map(|w| pred_edge[w[1]].expect("edge recorded for every pred")).collect();
+        Some(Cycle { nodes, edges })
+    }
+}
+
+/// A negative cycle found by [`Graph::find_negative_cycle`]: the node path
+/// plus the exact edge (pool) used between each consecutive pair. Kept apart
+/// from the nodes so `requote_cycle` re-quotes the pool that actually produced
+/// the cycle, not whichever pool happens to connect the same two tokens.
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    pub nodes: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+/// Re-quotes a detected cycle with real integer amounts via `quote_pool`
+/// (whichever quoter backs each edge's pool), since the `-ln` edge weights
+/// ignore price impact and only real quotes confirm the net after gas clears
+/// the profit threshold. `quote_pool` returns both the quoted output amount
+/// and that leg's gas units, which are summed across the cycle.
+pub async fn requote_cycle<F, Fut>(graph: &Graph, cycle: &Cycle, start_amount: U256, mut quote_pool: F) -> Result<(U256, U256)>
+where
+    F: FnMut(usize, U256) -> Fut,
+    Fut: Future<Output = Result<(U256, U256)>>,
+{
+    let mut amount = start_amount;
+    let mut gas_units = U256::zero();
+    for &edge_index in &cycle.edges {
+        let edge = graph.edges.get(edge_index).context("cycle edge index out of range")?;
+        let (out, leg_gas) = quote_pool(edge.pool_index, amount).await?;
+        amount = out;
+        gas_units += leg_gas;
+    }
+    Ok((amount, gas_units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(pool_index: usize, from: usize, to: usize, weight: f64) -> Edge {
+        Edge { pool_index, from, to, weight }
+    }
+
+    #[test]
+    fn find_negative_cycle_reconstructs_nodes_and_their_exact_edges() {
+        // A 3-node ring 0->1->2->0, each leg weighted -1.0 (a guaranteed
+        // negative cycle), source = 0.
+        let graph = Graph {
+            node_count: 3,
+            edges: vec![edge(0, 0, 1, -1.0), edge(1, 1, 2, -1.0), edge(2, 2, 0, -1.0)],
+        };
+        let cycle = graph.find_negative_cycle(0).expect("ring graph has a negative cycle");
+
+        // Every consecutive node pair must actually be joined by the edge
+        // recorded at that position, in the direction the cycle walks it.
+        assert_eq!(cycle.nodes.len(), cycle.edges.len() + 1);
+        for (pair, &edge_index) in cycle.nodes.windows(2).zip(&cycle.edges) {
+            let e = &graph.edges[edge_index];
+            assert_eq!((e.from, e.to), (pair[0], pair[1]));
+        }
+        // And it must close: first and last node are the same.
+        assert_eq!(cycle.nodes.first(), cycle.nodes.last());
+    }
+
+    #[test]
+    fn find_negative_cycle_returns_none_for_an_acyclic_graph() {
+        // A plain chain 0->1->2 has no cycle at all, negative or otherwise.
+        let graph = Graph { node_count: 3, edges: vec![edge(0, 0, 1, -1.0), edge(1, 1, 2, -1.0)] };
+        assert!(graph.find_negative_cycle(0).is_none());
+    }
+
+    #[test]
+    fn find_negative_cycle_picks_the_pool_specific_edge_on_parallel_edges() {
+        // Two pools both connect 0->1 (pool_index 0 and 1); only the second
+        // one is cheap enough to sit on a negative cycle with 1->0. Bellman-
+        // Ford must end up using edge index 1 for the 0->1 leg, not edge 0,
+        // even though both edges share the same (from, to) endpoints.
+        let graph = Graph {
+            node_count: 2,
+            edges: vec![edge(0, 0, 1, 5.0), edge(1, 0, 1, -1.0), edge(2, 1, 0, -1.0)],
+        };
+        let cycle = graph.find_negative_cycle(0).expect("graph has a negative cycle via the cheap parallel edge");
+        for (pair, &edge_index) in cycle.nodes.windows(2).zip(&cycle.edges) {
+            if pair == [0, 1] {
+                assert_eq!(edge_index, 1, "must pick the cheap parallel edge, not the expensive one sharing its endpoints");
+            }
+        }
+    }
+}
@@ -1,10 +1,18 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use dotenvy::dotenv;
 use ethers::prelude::*;
-use std::{env, sync::Arc, time::Duration};
+use std::{collections::HashMap, env, sync::Arc, time::Duration};
 use tokio::time::sleep;
-use std::fs::OpenOptions;
-use std::io::Write;
+
+mod backtest;
+mod gas;
+mod graph;
+mod money;
+mod pool_sim;
+mod record;
+mod stableswap;
+
+use money::{SignedUsdc, Usdc, Weth};
 
 // ---- Uniswap v3 QuoterV2 (JSON ABI because of tuple param) ----
 abigen!(
@@ -34,6 +42,18 @@ abigen!(
     }]"#
 );
 
+// ---- Minimal ERC20 (decimals only, to size a nominal probe per token) ----
+abigen!(
+    Erc20Decimals,
+    r#"[{
+      "inputs": [],
+      "name": "decimals",
+      "outputs": [{ "internalType": "uint8", "name": "", "type": "uint8" }],
+      "stateMutability": "view",
+      "type": "function"
+    }]"#
+);
+
 // ---- QuickSwap v3 (Algebra) Quoter ----
 abigen!(
     AlgebraQuoter,
@@ -57,8 +77,37 @@ abigen!(
 async fn main() -> Result<()> {
     dotenv().ok();
 
+    let args: Vec<String> = env::args().collect();
+
+    // `--replay <file>` just prints back a previously logged arb_log.jsonl
+    // and exits; it needs no RPC connection, so it's handled before anything
+    // below that requires one.
+    if let Some(path) = record::requested_replay_path(&args) {
+        return replay_records(path);
+    }
+
+    let live_rpc_url = env::var("POLYGON_RPC_URL")?;
+
+    // `--backtest <block>` forks Polygon at that block via Anvil and points the
+    // provider at the local fork instead of the live RPC; everything below
+    // runs unmodified against either one. `_anvil` must stay alive for the
+    // run's duration or the forked node shuts down.
+    let _anvil;
+    let rpc_url = match backtest::requested_fork_block(&args)? {
+        Some(block) => {
+            println!("Backtesting against a fork pinned at block {block}");
+            let anvil = backtest::spawn_fork(&live_rpc_url, block)?;
+            let endpoint = anvil.endpoint();
+            _anvil = Some(anvil);
+            endpoint
+        }
+        None => {
+            _anvil = None;
+            live_rpc_url
+        }
+    };
+
     // Provider
-    let rpc_url = env::var("POLYGON_RPC_URL")?;
     let provider = Arc::new(
         Provider::<Http>::try_from(rpc_url)?
             .interval(Duration::from_millis(250))
@@ -68,83 +117,147 @@ async fn main() -> Result<()> {
     let block = provider.get_block_number().await?;
     println!("Polygon latest block: {block}");
 
+    let chain_id = provider.get_chainid().await?.as_u64();
+
     // Addresses
     let weth: Address = env::var("WETH")?.parse()?;
     let usdc: Address = env::var("USDC")?.parse()?;
+    let wmatic: Address = env::var("WMATIC")?.parse()?;
     let uni_quoter_addr: Address = env::var("UNISWAP_QUOTER")?.parse()?;
     let quick_quoter_addr: Address = env::var("QUICKSWAP_QUOTER")?.parse()?;
+    let uni_pool_addr: Address = env::var("UNISWAP_POOL")?.parse()?;
+    let quick_pool_addr: Address = env::var("QUICKSWAP_POOL")?.parse()?;
 
     // Contracts
     let uni_quoter = UniswapQuoterV2::new(uni_quoter_addr, provider.clone());
     let quick_quoter = AlgebraQuoter::new(quick_quoter_addr, provider.clone());
+    let uni_pool = pool_sim::UniswapV3Pool::new(uni_pool_addr, provider.clone());
+    let quick_pool = pool_sim::AlgebraPool::new(quick_pool_addr, provider.clone());
+
+    // `token0`/`token1` never change, so these are only read once; they tell
+    // us which swap direction (`zero_for_one`) corresponds to USDC->WETH on
+    // each pool when we quote locally below.
+    let uni_zero_for_one_usdc_to_weth = uni_pool.token_0().call().await? == usdc;
+    let quick_zero_for_one_usdc_to_weth = quick_pool.token_0().call().await? == usdc;
 
     // Config
     let fee: u32 = env::var("UNIV3_FEE")?.parse()?;              // Uni v3 fee tier (uint24)
-    let start_usdc_f: f64 = env::var("START_USDC").unwrap_or("10000".into()).parse().unwrap_or(10000.0);
-    let start_usdc = to_units(start_usdc_f, 6);                   // USDC 6 decimals
-
-    // Gas estimate (USDC). For round trip assume 2 tx (router/approvals excluded here)
-    let gas_usdc_per_tx_f: f64 = env::var("GAS_USDC_PER_TX").unwrap_or("0.02".into()).parse().unwrap_or(0.02);
-    let gas_usdc_per_tx = to_units(gas_usdc_per_tx_f, 6);
-    let round_trip_gas = gas_usdc_per_tx.checked_mul(U256::from(2)).unwrap_or_else(U256::zero);
+    // WMATIC/USDC fee tier used only to price gas into USDC; defaults to the same tier as the arb pair.
+    let wmatic_fee: u32 = env::var("WMATIC_USDC_FEE").ok().and_then(|v| v.parse().ok()).unwrap_or(fee);
+    let start_usdc: Usdc = env::var("START_USDC").unwrap_or("10000".into()).parse()?;
 
     // Profit threshold (USDC)
-    let profit_threshold_f: f64 = env::var("PROFIT_THRESHOLD").unwrap_or("0.1".into()).parse().unwrap_or(0.1);
-    let profit_threshold = to_units(profit_threshold_f, 6);
+    let profit_threshold: Usdc = env::var("PROFIT_THRESHOLD").unwrap_or("0.1".into()).parse()?;
+
+    // `--graph <config.json>` scans an arbitrary token/pool graph for
+    // negative-weight cycles instead of the fixed WETH/USDC pair below.
+    if let Some(config_path) = graph::requested_config_path(&args) {
+        let config_path = config_path.to_string();
+        let quoters = Quoters { uni: &uni_quoter, quick: &quick_quoter, provider: &provider };
+        return run_graph_mode(&config_path, &quoters, usdc, wmatic, wmatic_fee, profit_threshold).await;
+    }
 
-    let zero_u160 = U256::zero(); // no sqrt price limit
+    // Cached pool state, refreshed only when the block number advances so the
+    // scan loop costs zero RPC calls between blocks. `Provider<Http>` has no
+    // `eth_subscribe`, so "subscribing to blocks" here means polling
+    // `get_block_number` once per iteration instead of watching a pubsub feed.
+    let mut synced_block: Option<U64> = None;
+    let mut uni_state: Option<pool_sim::PoolState> = None;
+    let mut quick_state: Option<pool_sim::PoolState> = None;
 
     loop {
-        // ---------- PATH A: Uni BUY (USDC->WETH) -> Quick SELL (WETH->USDC) ----------
-        let mut a_ok = false;
-        let mut weth_a = U256::zero();
-        let mut back_a = U256::zero();
-
-        // Buy WETH on Uni
-        let buy_params_a = uniswap_quoter_v2::QuoteExactInputSingleParams {
-            token_in: usdc,
-            token_out: weth,
-            amount_in: start_usdc,
-            fee,
-            sqrt_price_limit_x96: zero_u160,
+        // Effective EIP-1559 gas price for this round: base fee of the pending
+        // block plus a priority tip (falls back to an eth_feeHistory percentile).
+        let effective_gas_price = match gas::effective_gas_price(&provider).await {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Gas price lookup failed, skipping round: {e:?}");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
         };
-        match uni_quoter.quote_exact_input_single(buy_params_a).call().await {
-            Ok((weth_out, _, _, _)) => {
-                // Sell WETH on Quick
-                match quick_quoter.quote_exact_input_single(weth, usdc, weth_out, zero_u160).call().await {
-                    Ok(usdc_back) => { a_ok = true; weth_a = weth_out; back_a = usdc_back; }
-                    Err(e) => eprintln!("QuickSwap quote error (A WETH->USDC): {e:?}"),
+
+        let current_block = provider.get_block_number().await?;
+        if synced_block != Some(current_block) {
+            let refreshed = async {
+                let uni = pool_sim::PoolState::fetch(&uni_pool).await?;
+                let quick = pool_sim::PoolState::fetch_algebra(&quick_pool).await?;
+                Ok::<_, anyhow::Error>((uni, quick))
+            }
+            .await;
+            match refreshed {
+                Ok((uni, quick)) => {
+                    uni_state = Some(uni);
+                    quick_state = Some(quick);
+                    synced_block = Some(current_block);
                 }
+                Err(e) => eprintln!("Pool state refresh failed, quoting against last-known state: {e:?}"),
             }
-            Err(e) => eprintln!("Uniswap quote error (A USDC->WETH): {e:?}"),
+        }
+
+        let (Some(uni_pool_state), Some(quick_pool_state)) = (&uni_state, &quick_state) else {
+            eprintln!("No pool state yet, skipping round.");
+            sleep(Duration::from_secs(5)).await;
+            continue;
+        };
+
+        // Both legs are quoted locally now, so neither carries a live
+        // `gasEstimate`; each path burns one Uniswap-shaped leg and one
+        // Algebra-shaped leg, so the round-trip gas units are the same either way.
+        let gas_units = (gas::RoundTripGasUnits {
+            uni_leg: U256::from(gas::UNISWAP_GAS_UNITS_DEFAULT),
+            algebra_leg: U256::from(gas::ALGEBRA_GAS_UNITS_DEFAULT),
+        })
+        .total();
+
+        // ---------- PATH A: Uni BUY (USDC->WETH) -> Quick SELL (WETH->USDC) ----------
+        let mut a_ok = false;
+        let mut weth_a = Weth::zero();
+        let mut back_a = Usdc::zero();
+
+        match pool_sim::quote_exact_input_single(uni_pool_state, uni_zero_for_one_usdc_to_weth, start_usdc.0) {
+            Ok(weth_out) => match pool_sim::quote_exact_input_single(quick_pool_state, !quick_zero_for_one_usdc_to_weth, weth_out) {
+                Ok(usdc_back) => {
+                    a_ok = true;
+                    weth_a = Weth::from_raw(weth_out);
+                    back_a = Usdc::from_raw(usdc_back);
+                }
+                Err(e) => eprintln!("QuickSwap local quote error (A WETH->USDC): {e:?}"),
+            },
+            Err(e) => eprintln!("Uniswap local quote error (A USDC->WETH): {e:?}"),
         }
 
         // ---------- PATH B: Quick BUY (USDC->WETH) -> Uni SELL (WETH->USDC) ----------
         let mut b_ok = false;
-        let mut weth_b = U256::zero();
-        let mut back_b = U256::zero();
-
-        match quick_quoter.quote_exact_input_single(usdc, weth, start_usdc, zero_u160).call().await {
-            Ok(weth_out) => {
-                let sell_params_b = uniswap_quoter_v2::QuoteExactInputSingleParams {
-                    token_in: weth,
-                    token_out: usdc,
-                    amount_in: weth_out,
-                    fee,
-                    sqrt_price_limit_x96: zero_u160,
-                };
-                match uni_quoter.quote_exact_input_single(sell_params_b).call().await {
-                    Ok((usdc_back, _, _, _)) => { b_ok = true; weth_b = weth_out; back_b = usdc_back; }
-                    Err(e) => eprintln!("Uniswap quote error (B WETH->USDC): {e:?}"),
+        let mut weth_b = Weth::zero();
+        let mut back_b = Usdc::zero();
+
+        match pool_sim::quote_exact_input_single(quick_pool_state, quick_zero_for_one_usdc_to_weth, start_usdc.0) {
+            Ok(weth_out) => match pool_sim::quote_exact_input_single(uni_pool_state, !uni_zero_for_one_usdc_to_weth, weth_out) {
+                Ok(usdc_back) => {
+                    b_ok = true;
+                    weth_b = Weth::from_raw(weth_out);
+                    back_b = Usdc::from_raw(usdc_back);
                 }
-            }
-            Err(e) => eprintln!("QuickSwap quote error (B USDC->WETH): {e:?}"),
+                Err(e) => eprintln!("Uniswap local quote error (B WETH->USDC): {e:?}"),
+            },
+            Err(e) => eprintln!("QuickSwap local quote error (B USDC->WETH): {e:?}"),
         }
 
+        // Convert the round-trip wei gas cost into USDC by quoting WMATIC->USDC on
+        // Uni. Both paths burn the same gas_units * effective_gas_price, so quote
+        // it once and reuse it rather than hitting the RPC twice for one value.
+        let round_trip_gas = match gas_cost_usdc(&uni_quoter, wmatic, usdc, wmatic_fee, gas_units * effective_gas_price).await {
+            Ok(g) => g,
+            Err(e) => { eprintln!("Gas->USDC quote failed: {e:?}"); Usdc::zero() }
+        };
+        let round_trip_gas_a = round_trip_gas;
+        let round_trip_gas_b = round_trip_gas;
+
         // ----- Print both paths with signed diffs -----
         if a_ok {
             println!("\n--- PATH A: Uni BUY â†’ Quick SELL ---");
-            pretty_path(start_usdc, weth_a, back_a, round_trip_gas, "Uni BUY", "Quick SELL");
+            pretty_path(start_usdc, weth_a, back_a, round_trip_gas_a, "Uni BUY", "Quick SELL");
         } else {
             eprintln!("\n--- PATH A: Uni BUY â†’ Quick SELL ---");
             eprintln!("Quote failed.");
@@ -152,24 +265,24 @@ async fn main() -> Result<()> {
 
         if b_ok {
             println!("\n--- PATH B: Quick BUY â†’ Uni SELL ---");
-            pretty_path(start_usdc, weth_b, back_b, round_trip_gas, "Quick BUY", "Uni SELL");
+            pretty_path(start_usdc, weth_b, back_b, round_trip_gas_b, "Quick BUY", "Uni SELL");
         } else {
             eprintln!("\n--- PATH B: Quick BUY â†’ Uni SELL ---");
             eprintln!("Quote failed.");
         }
 
-        // ----- Choose the better path (highest USDC back) -----
-        let (best_label, best_weth, best_back, best_ok) = match (a_ok, b_ok) {
+        // ----- Choose the better path (highest USDC back net of its own gas) -----
+        let (best_label, best_weth, best_back, best_gas, best_ok) = match (a_ok, b_ok) {
             (true, true) => {
-                if back_a > back_b {
-                    ("Uni BUY â†’ Quick SELL", weth_a, back_a, true)
+                if SignedUsdc::net(back_a, start_usdc, round_trip_gas_a)? > SignedUsdc::net(back_b, start_usdc, round_trip_gas_b)? {
+                    ("Uni BUY â†’ Quick SELL", weth_a, back_a, round_trip_gas_a, true)
                 } else {
-                    ("Quick BUY â†’ Uni SELL", weth_b, back_b, true)
+                    ("Quick BUY â†’ Uni SELL", weth_b, back_b, round_trip_gas_b, true)
                 }
             }
-            (true, false) => ("Uni BUY â†’ Quick SELL", weth_a, back_a, true),
-            (false, true) => ("Quick BUY â†’ Uni SELL", weth_b, back_b, true),
-            (false, false) => ("", U256::zero(), U256::zero(), false),
+            (true, false) => ("Uni BUY â†’ Quick SELL", weth_a, back_a, round_trip_gas_a, true),
+            (false, true) => ("Quick BUY â†’ Uni SELL", weth_b, back_b, round_trip_gas_b, true),
+            (false, false) => ("", Weth::zero(), Usdc::zero(), Usdc::zero(), false),
         };
 
         if !best_ok {
@@ -179,38 +292,35 @@ async fn main() -> Result<()> {
         }
 
         // Signed net = back - start - gas
-        let net_i128 = signed_diff(best_back, start_usdc, round_trip_gas);
-        let net_abs_u256 = if net_i128 >= 0 { U256::from(net_i128 as u128) } else { U256::from((-net_i128) as u128) };
-        let net_str = if net_i128 >= 0 {
-            fmt_units(net_abs_u256, 6)
-        } else {
-            format!("-{}", fmt_units(net_abs_u256, 6))
-        };
+        let net = SignedUsdc::net(best_back, start_usdc, best_gas)?;
 
         println!("\n=== Best Path Selected: {} ===", best_label);
         println!(
             "Start: {} USDC | WETH bought: {} | USDC back: {} | Gas: {} | Net: {}",
-            fmt_units(start_usdc, 6),
-            fmt_units(best_weth, 18),
-            fmt_units(best_back, 6),
-            fmt_units(round_trip_gas, 6),
-            net_str
+            start_usdc, best_weth, best_back, best_gas, net
         );
 
         // Threshold check using signed math
-        let thresh_i128 = profit_threshold.as_u128() as i128;
-        if net_i128 > thresh_i128 {
-            println!("  ðŸš€ðŸš€ ARB DETECTED ({}): {} USDC", best_label, net_str);
-            let log_entry = format!(
-                "ARB ({label}): net={net} USDC | start={start} USDC | weth_bought={weth} | usdc_back={back} | gas={gas}\n",
-                label = best_label,
-                net = net_str,
-                start = fmt_units(start_usdc,6),
-                weth = fmt_units(best_weth,18),
-                back = fmt_units(best_back,6),
-                gas = fmt_units(round_trip_gas,6)
-            );
-            append_to_file("profit.txt", &log_entry);
+        if net > profit_threshold {
+            println!("  ðŸš€ðŸš€ ARB DETECTED ({}): {} USDC", best_label, net);
+            let current_block = provider.get_block_number().await?;
+            let record = record::ArbRecord {
+                timestamp_unix: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                block_number: current_block.as_u64(),
+                chain_id,
+                path_label: best_label.to_string(),
+                start_usdc: start_usdc.0,
+                weth_bought: best_weth.0,
+                usdc_back: best_back.0,
+                gas_usdc: best_gas.0,
+                net_usdc: net.to_string(),
+            };
+            if let Err(e) = record::append_record("arb_log.jsonl", &record) {
+                eprintln!("Failed to write arb_log.jsonl: {e:?}");
+            }
         } else {
             println!("No arbitrage (net â‰¤ threshold).");
         }
@@ -221,12 +331,228 @@ async fn main() -> Result<()> {
 
 // ---------------- helpers ----------------
 
-fn to_units(amount: f64, decimals: u32) -> U256 {
-    // For config-like inputs; for production, prefer integer math end-to-end.
-    let scale = 10u128.pow(decimals);
-    U256::from((amount * scale as f64).round() as u128)
+/// Looks up `token`'s ERC20 `decimals()`, caching the result since it never
+/// changes; used to size each pool's probe trade in graph mode.
+async fn token_decimals(provider: &Arc<Provider<Http>>, cache: &mut HashMap<Address, u32>, token: Address) -> Result<u32> {
+    if let Some(&decimals) = cache.get(&token) {
+        return Ok(decimals);
+    }
+    let decimals = Erc20Decimals::new(token, provider.clone())
+        .decimals()
+        .call()
+        .await
+        .with_context(|| format!("decimals() for {token:?}"))? as u32;
+    cache.insert(token, decimals);
+    Ok(decimals)
+}
+
+/// Bundles the three quoter handles `quote_pool` and `run_graph_mode` thread
+/// around together, so passing them doesn't push either function past
+/// clippy's too-many-arguments threshold.
+struct Quoters<'a> {
+    uni: &'a UniswapQuoterV2<Provider<Http>>,
+    quick: &'a AlgebraQuoter<Provider<Http>>,
+    provider: &'a Arc<Provider<Http>>,
 }
 
+/// Quotes one graph edge through whichever quoter backs its venue. Returns
+/// the quoted output amount alongside that leg's gas units: a real
+/// `gasEstimate` for Uniswap (the only quoter here that hands one back), and
+/// a configured default for Algebra/StableSwap.
+async fn quote_pool(quoters: &Quoters<'_>, pool: &graph::PoolConfig, amount_in: U256) -> Result<(U256, U256)> {
+    match pool.venue {
+        graph::Venue::UniswapV3 => {
+            let params = uniswap_quoter_v2::QuoteExactInputSingleParams {
+                token_in: pool.token_in,
+                token_out: pool.token_out,
+                amount_in,
+                fee: pool.fee_ppm,
+                sqrt_price_limit_x96: U256::zero(),
+            };
+            let (amount_out, _, _, gas_estimate) = quoters.uni.quote_exact_input_single(params).call().await?;
+            Ok((amount_out, gas_estimate))
+        }
+        graph::Venue::Algebra => {
+            let amount_out = quoters
+                .quick
+                .quote_exact_input_single(pool.token_in, pool.token_out, amount_in, U256::zero())
+                .call()
+                .await?;
+            Ok((amount_out, U256::from(gas::ALGEBRA_GAS_UNITS_DEFAULT)))
+        }
+        graph::Venue::StableSwap => {
+            let coin_in = pool.coin_in_index.context("StableSwap pool config missing coin_in_index")?;
+            let coin_out = pool.coin_out_index.context("StableSwap pool config missing coin_out_index")?;
+            let n_coins = pool.n_coins.context("StableSwap pool config missing n_coins")?;
+            let curve_pool = stableswap::CurvePool::new(pool.pool, quoters.provider.clone());
+            let state = stableswap::StablePoolState::fetch(&curve_pool, n_coins).await?;
+            let amount_out = stableswap::quote_exact_input(&state, coin_in, coin_out, amount_in)?;
+            Ok((amount_out, U256::from(gas::STABLESWAP_GAS_UNITS_DEFAULT)))
+        }
+    }
+}
+
+/// `--graph <config.json>` entry point: scans the configured token/pool graph
+/// for a negative-weight (gross-profitable) cycle each round, then re-quotes
+/// it with real integer amounts and gas to confirm it clears
+/// `PROFIT_THRESHOLD` before logging it the same way the fixed-pair loop does.
+async fn run_graph_mode(
+    config_path: &str,
+    quoters: &Quoters<'_>,
+    usdc: Address,
+    wmatic: Address,
+    wmatic_fee: u32,
+    profit_threshold: Usdc,
+) -> Result<()> {
+    let config = graph::GraphConfig::load(config_path)?;
+    println!("Graph mode: {} tokens, {} pools", config.tokens.len(), config.pools.len());
+
+    let usdc_node = config.tokens.iter().position(|t| *t == usdc);
+    // Every token's decimals, fetched once and reused: a fixed base-unit probe
+    // would be a dust trade for an 18-decimal token and would never price a
+    // 6-decimal one, so each pool is probed with exactly "1.0" of its own
+    // token_in instead.
+    let mut decimals_cache: HashMap<Address, u32> = HashMap::new();
+
+    loop {
+        let mut rates = Vec::with_capacity(config.pools.len());
+        for pool in &config.pools {
+            let rate = async {
+                let decimals_in = token_decimals(quoters.provider, &mut decimals_cache, pool.token_in).await?;
+                let decimals_out = token_decimals(quoters.provider, &mut decimals_cache, pool.token_out).await?;
+                let probe_amount = U256::from(10u64).pow(U256::from(decimals_in));
+                let (out, _gas_units) = quote_pool(quoters, pool, probe_amount).await?;
+                Ok::<f64, anyhow::Error>(out.as_u128() as f64 / 10f64.powi(decimals_out as i32))
+            }
+            .await;
+            match rate {
+                Ok(r) => rates.push(r),
+                Err(e) => {
+                    eprintln!("Graph: quote failed for pool {:?}: {e:?}", pool.pool);
+                    // Graph::build skips any edge whose rate is non-positive,
+                    // rather than failing the whole build over one bad pool.
+                    rates.push(0.0);
+                }
+            }
+        }
+
+        let g = match graph::Graph::build(&config, &rates) {
+            Ok(g) => g,
+            Err(e) => {
+                eprintln!("Graph: failed to build this round: {e:?}");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(source) = usdc_node else {
+            eprintln!("Graph: USDC isn't in the configured token list, nothing to scan from.");
+            sleep(Duration::from_secs(5)).await;
+            continue;
+        };
+
+        match g.find_negative_cycle(source) {
+            Some(cycle) if cycle.nodes.first() == Some(&source) => {
+                println!("Negative cycle found: {:?}", cycle.nodes);
+                // A more realistic re-quote size: 1000 whole USDC (the cycle
+                // always starts at `source`, which is USDC by construction).
+                let usdc_decimals = token_decimals(quoters.provider, &mut decimals_cache, usdc).await?;
+                let start_amount = U256::from(1000) * U256::from(10u64).pow(U256::from(usdc_decimals));
+
+                match graph::requote_cycle(&g, &cycle, start_amount, |pool_index, amount_in| {
+                    quote_pool(quoters, &config.pools[pool_index], amount_in)
+                })
+                .await
+                {
+                    Ok((back, gas_units)) => {
+                        let gas_usdc = match gas::effective_gas_price(quoters.provider).await {
+                            Ok(price) => gas_cost_usdc(quoters.uni, wmatic, usdc, wmatic_fee, gas_units * price).await.unwrap_or_else(|e| {
+                                eprintln!("Graph: gas->USDC quote failed: {e:?}");
+                                Usdc::zero()
+                            }),
+                            Err(e) => {
+                                eprintln!("Graph: gas price lookup failed: {e:?}");
+                                Usdc::zero()
+                            }
+                        };
+
+                        let net = SignedUsdc::net(Usdc::from_raw(back), Usdc::from_raw(start_amount), gas_usdc)?;
+                        println!("  Re-quoted net (after gas): {net} USDC");
+
+                        if net > profit_threshold {
+                            println!("  ðŸš€ðŸš€ ARB DETECTED (graph cycle): {net} USDC");
+                            let record = record::ArbRecord {
+                                timestamp_unix: std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .unwrap_or_default()
+                                    .as_secs(),
+                                block_number: quoters.provider.get_block_number().await?.as_u64(),
+                                chain_id: quoters.provider.get_chainid().await?.as_u64(),
+                                path_label: format!("graph cycle {:?}", cycle.nodes),
+                                start_usdc: start_amount,
+                                weth_bought: U256::zero(),
+                                usdc_back: back,
+                                gas_usdc: gas_usdc.0,
+                                net_usdc: net.to_string(),
+                            };
+                            if let Err(e) = record::append_record("arb_log.jsonl", &record) {
+                                eprintln!("Failed to write arb_log.jsonl: {e:?}");
+                            }
+                        } else {
+                            println!("  No arbitrage (net â‰¤ threshold).");
+                        }
+                    }
+                    Err(e) => eprintln!("Graph: re-quote failed: {e:?}"),
+                }
+            }
+            Some(cycle) => println!("Negative cycle found but doesn't pass through USDC, skipping: {:?}", cycle.nodes),
+            None => println!("Graph: no negative cycle this round."),
+        }
+
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// `--replay <file>` entry point: reads back a previously logged
+/// `arb_log.jsonl` and prints each record, so a past run can be inspected
+/// without re-scanning the chain.
+fn replay_records(path: &str) -> Result<()> {
+    let records = record::read_records(path)?;
+    println!("Replaying {} record(s) from {path}", records.len());
+    for record in &records {
+        println!(
+            "[block {}] {} | start {} USDC -> back {} USDC | gas {} USDC | net {} USDC",
+            record.block_number, record.path_label, record.start_usdc, record.usdc_back, record.gas_usdc, record.net_usdc
+        );
+    }
+    Ok(())
+}
+
+/// Converts a wei gas cost into USDC by quoting WMATIC->USDC through the Uni
+/// v3 quoter, so `SignedUsdc::net` stays in like units.
+async fn gas_cost_usdc(
+    uni_quoter: &UniswapQuoterV2<Provider<Http>>,
+    wmatic: Address,
+    usdc: Address,
+    fee: u32,
+    wei_amount: U256,
+) -> Result<Usdc> {
+    if wei_amount.is_zero() {
+        return Ok(Usdc::zero());
+    }
+    let params = uniswap_quoter_v2::QuoteExactInputSingleParams {
+        token_in: wmatic,
+        token_out: usdc,
+        amount_in: wei_amount,
+        fee,
+        sqrt_price_limit_x96: U256::zero(),
+    };
+    let (usdc_out, _, _, _) = uni_quoter.quote_exact_input_single(params).call().await?;
+    Ok(Usdc::from_raw(usdc_out))
+}
+
+/// Display-only formatter for derived quantities (implied rates) that aren't
+/// a stored [`Usdc`]/[`Weth`] amount.
 fn fmt_units(amount: U256, decimals: u32) -> String {
     if decimals == 0 { return amount.to_string(); }
     let ten = U256::from(10);
@@ -238,35 +564,26 @@ fn fmt_units(amount: U256, decimals: u32) -> String {
     if frac.is_empty() { int.to_string() } else { format!("{}.{}", int, frac) }
 }
 
-/// Signed profit in USDC (i128) = back - start - gas (all USDC, 6dp)
-fn signed_diff(back: U256, start: U256, gas: U256) -> i128 {
-    let b = back.as_u128() as i128;
-    let s = start.as_u128() as i128;
-    let g = gas.as_u128() as i128;
-    b - s - g
-}
-
 /// Pretty print a path with implied rates and **signed** diffs
-fn pretty_path(start_usdc: U256, weth_bought: U256, usdc_back: U256, gas_usdc: U256, buy_tag: &str, sell_tag: &str) {
-    let buy_rate_weth_per_usdc = ratio_string(weth_bought, start_usdc, 18, 6); // WETH per 1 USDC
-    let sell_rate_usdc_per_weth = ratio_string(usdc_back, weth_bought, 6, 18); // USDC per 1 WETH
-
-    // gross signed (no gas) and net signed (with gas)
-    let gross_i128 = signed_diff(usdc_back, start_usdc, U256::zero());
-    let net_i128   = signed_diff(usdc_back, start_usdc, gas_usdc);
-
-    let gross_abs = if gross_i128 >= 0 { U256::from(gross_i128 as u128) } else { U256::from((-gross_i128) as u128) };
-    let net_abs   = if net_i128 >= 0 { U256::from(net_i128 as u128) } else { U256::from((-net_i128) as u128) };
-
-    let gross_str = if gross_i128 >= 0 { fmt_units(gross_abs, 6) } else { format!("-{}", fmt_units(gross_abs, 6)) };
-    let net_str   = if net_i128   >= 0 { fmt_units(net_abs,   6) } else { format!("-{}", fmt_units(net_abs,   6)) };
-
-    println!("Start: {} USDC", fmt_units(start_usdc, 6));
-    println!("{}: {} WETH (â‰ˆ {} WETH/USDC)", buy_tag, fmt_units(weth_bought, 18), buy_rate_weth_per_usdc);
-    println!("{}: {} USDC (â‰ˆ {} USDC/WETH)", sell_tag, fmt_units(usdc_back, 6),  sell_rate_usdc_per_weth);
-    println!("Gross diff: {} USDC", gross_str);
-    println!("Est. gas (round-trip): {} USDC", fmt_units(gas_usdc, 6));
-    println!("Net Profit: {} USDC", net_str);
+fn pretty_path(start_usdc: Usdc, weth_bought: Weth, usdc_back: Usdc, gas_usdc: Usdc, buy_tag: &str, sell_tag: &str) {
+    let buy_rate_weth_per_usdc = ratio_string(weth_bought.0, start_usdc.0, 18, 6); // WETH per 1 USDC
+    let sell_rate_usdc_per_weth = ratio_string(usdc_back.0, weth_bought.0, 6, 18); // USDC per 1 WETH
+
+    let gross = SignedUsdc::net(usdc_back, start_usdc, Usdc::zero()).unwrap_or_else(|e| {
+        eprintln!("pretty_path: gross diff overflow: {e:?}");
+        SignedUsdc::zero()
+    });
+    let net = SignedUsdc::net(usdc_back, start_usdc, gas_usdc).unwrap_or_else(|e| {
+        eprintln!("pretty_path: net overflow: {e:?}");
+        SignedUsdc::zero()
+    });
+
+    println!("Start: {start_usdc} USDC");
+    println!("{buy_tag}: {weth_bought} WETH (â‰ˆ {buy_rate_weth_per_usdc} WETH/USDC)");
+    println!("{sell_tag}: {usdc_back} USDC (â‰ˆ {sell_rate_usdc_per_weth} USDC/WETH)");
+    println!("Gross diff: {gross} USDC");
+    println!("Est. gas (round-trip): {gas_usdc} USDC");
+    println!("Net Profit: {net} USDC");
 }
 
 /// Safe ratio as a string: (num / den) with decimals, scaled to 18dp for display
@@ -290,13 +607,3 @@ fn ratio_string(num: U256, den: U256, num_decimals: u32, den_decimals: u32) -> S
     let q = num_scaled / den;
     fmt_units(q, target_dp)
 }
-
-fn append_to_file(path: &str, line: &str) {
-    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
-        if let Err(e) = file.write_all(line.as_bytes()) {
-            eprintln!("Failed to write to {path}: {e:?}");
-        }
-    } else {
-        eprintln!("Failed to open {path}");
-    }
-}
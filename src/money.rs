@@ -0,0 +1,169 @@
+//! Fixed-point money newtypes.
+//!
+//! `to_units` used to multiply an `f64` by `10^decimals` and round, which
+//! silently loses precision above ~2^53, and `signed_diff` went through
+//! `as_u128() as i128`, which truncates once a balance passes `u128::MAX`.
+//! `Usdc`/`Weth`/`SignedUsdc` carry their decimals and do checked arithmetic
+//! over `U256`/`I256` instead, so mixing a WETH amount into USDC math is a
+//! compile error and overflow is a `Result`, never a silent wrap.
+
+use anyhow::{anyhow, bail, Result};
+use ethers::types::{I256, U256};
+use std::fmt;
+use std::str::FromStr;
+
+pub const USDC_DECIMALS: u32 = 6;
+pub const WETH_DECIMALS: u32 = 18;
+
+/// Parses a decimal string (e.g. `"123.45"`) straight into base units at
+/// `decimals`, with no floating point involved.
+fn parse_decimal(s: &str, decimals: u32) -> Result<U256> {
+    let s = s.trim();
+    let (int_part, frac_part) = match s.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (s, ""),
+    };
+    if frac_part.len() > decimals as usize {
+        bail!("{s} has more than {decimals} decimal places");
+    }
+    let int_units = U256::from_dec_str(if int_part.is_empty() { "0" } else { int_part })
+        .map_err(|e| anyhow!("invalid integer part {int_part:?}: {e}"))?;
+    let mut frac_padded = frac_part.to_string();
+    while frac_padded.len() < decimals as usize {
+        frac_padded.push('0');
+    }
+    let frac_units = if frac_padded.is_empty() {
+        U256::zero()
+    } else {
+        U256::from_dec_str(&frac_padded).map_err(|e| anyhow!("invalid fractional part {frac_part:?}: {e}"))?
+    };
+    let scale = U256::from(10u8).pow(U256::from(decimals));
+    Ok(int_units * scale + frac_units)
+}
+
+/// Renders base units at `decimals` back to a trimmed decimal string.
+fn fmt_units(amount: U256, decimals: u32) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let scale = U256::from(10u8).pow(U256::from(decimals));
+    let int = amount / scale;
+    let mut frac = (amount % scale).to_string();
+    while frac.len() < decimals as usize {
+        frac.insert(0, '0');
+    }
+    while frac.ends_with('0') {
+        frac.pop();
+    }
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{int}.{frac}")
+    }
+}
+
+macro_rules! money_newtype {
+    ($name:ident, $decimals:expr) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct $name(pub U256);
+
+        impl $name {
+            pub fn zero() -> Self {
+                Self(U256::zero())
+            }
+
+            pub fn from_raw(units: U256) -> Self {
+                Self(units)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = anyhow::Error;
+            fn from_str(s: &str) -> Result<Self> {
+                parse_decimal(s, $decimals).map(Self)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", fmt_units(self.0, $decimals))
+            }
+        }
+    };
+}
+
+money_newtype!(Usdc, USDC_DECIMALS);
+money_newtype!(Weth, WETH_DECIMALS);
+
+/// A signed USDC amount, for net-profit diffs that can go negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SignedUsdc(pub I256);
+
+impl SignedUsdc {
+    pub fn zero() -> Self {
+        Self(I256::zero())
+    }
+
+    pub fn net(back: Usdc, start: Usdc, gas: Usdc) -> Result<Self> {
+        let back = I256::from_raw(back.0);
+        let start = I256::from_raw(start.0);
+        let gas = I256::from_raw(gas.0);
+        let gross = back.checked_sub(start).ok_or_else(|| anyhow!("SignedUsdc::net: back - start overflow"))?;
+        let net = gross.checked_sub(gas).ok_or_else(|| anyhow!("SignedUsdc::net: gross - gas overflow"))?;
+        Ok(Self(net))
+    }
+}
+
+impl fmt::Display for SignedUsdc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_negative() {
+            write!(f, "-{}", fmt_units((-self.0).into_raw(), USDC_DECIMALS))
+        } else {
+            write!(f, "{}", fmt_units(self.0.into_raw(), USDC_DECIMALS))
+        }
+    }
+}
+
+impl PartialEq<Usdc> for SignedUsdc {
+    fn eq(&self, other: &Usdc) -> bool {
+        self.0 == I256::from_raw(other.0)
+    }
+}
+
+impl PartialOrd<Usdc> for SignedUsdc {
+    fn partial_cmp(&self, other: &Usdc) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&I256::from_raw(other.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usdc_decimal_string_round_trips() {
+        let usdc: Usdc = "123.45".parse().unwrap();
+        assert_eq!(usdc.to_string(), "123.45");
+        assert_eq!(usdc.0, U256::from(123_450_000u64));
+    }
+
+    #[test]
+    fn usdc_trims_trailing_zeros_on_display() {
+        let usdc: Usdc = "100".parse().unwrap();
+        assert_eq!(usdc.to_string(), "100");
+    }
+
+    #[test]
+    fn usdc_rejects_too_many_decimal_places() {
+        assert!("1.2345678".parse::<Usdc>().is_err());
+    }
+
+    #[test]
+    fn signed_usdc_net_is_checked_and_can_go_negative() {
+        let start: Usdc = "100".parse().unwrap();
+        let back: Usdc = "90".parse().unwrap();
+        let gas: Usdc = "1".parse().unwrap();
+        let net = SignedUsdc::net(back, start, gas).unwrap();
+        assert_eq!(net.to_string(), "-11");
+    }
+}
@@ -0,0 +1,535 @@
+//! Local, zero-RPC simulation of `quoteExactInputSingle` for Uniswap-v3-style
+//! pools (this also covers Algebra/QuickSwap, whose pricing math is the same
+//! concentrated-liquidity curve, just read through a differently named ABI).
+//!
+//! The live loop used to hit the on-chain quoter every 5 seconds per path,
+//! which is slow and gets rate-limited, and it only ever probed one fixed
+//! trade size. Instead we read each pool's price/tick, liquidity, fee, and
+//! initialized-tick bitmap once per new block and keep a [`PoolState`] in
+//! memory; [`quote_exact_input_single`] then reproduces the pool's single-tick
+//! swap math in pure integer arithmetic, so scanning many trade sizes between
+//! blocks costs nothing.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ethers::prelude::*;
+use ethers::types::U512;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+// ---- Minimal Uniswap-v3 pool ABI (slot0 / liquidity / ticks / tickBitmap) ----
+abigen!(
+    UniswapV3Pool,
+    r#"[{
+      "inputs": [],
+      "name": "slot0",
+      "outputs": [
+        { "internalType": "uint160", "name": "sqrtPriceX96", "type": "uint160" },
+        { "internalType": "int24",   "name": "tick",         "type": "int24"  },
+        { "internalType": "uint16",  "name": "observationIndex", "type": "uint16" },
+        { "internalType": "uint16",  "name": "observationCardinality", "type": "uint16" },
+        { "internalType": "uint16",  "name": "observationCardinalityNext", "type": "uint16" },
+        { "internalType": "uint8",   "name": "feeProtocol",  "type": "uint8" },
+        { "internalType": "bool",    "name": "unlocked",     "type": "bool" }
+      ],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "liquidity",
+      "outputs": [{ "internalType": "uint128", "name": "", "type": "uint128" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "fee",
+      "outputs": [{ "internalType": "uint24", "name": "", "type": "uint24" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "tickSpacing",
+      "outputs": [{ "internalType": "int24", "name": "", "type": "int24" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "token0",
+      "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "token1",
+      "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [{ "internalType": "int16", "name": "wordPosition", "type": "int16" }],
+      "name": "tickBitmap",
+      "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [{ "internalType": "int24", "name": "tick", "type": "int24" }],
+      "name": "ticks",
+      "outputs": [
+        { "internalType": "uint128", "name": "liquidityGross", "type": "uint128" },
+        { "internalType": "int128",  "name": "liquidityNet",   "type": "int128"  },
+        { "internalType": "uint256", "name": "feeGrowthOutside0X128", "type": "uint256" },
+        { "internalType": "uint256", "name": "feeGrowthOutside1X128", "type": "uint256" },
+        { "internalType": "int56",   "name": "tickCumulativeOutside", "type": "int56" },
+        { "internalType": "uint160", "name": "secondsPerLiquidityOutsideX128", "type": "uint160" },
+        { "internalType": "uint32",  "name": "secondsOutside", "type": "uint32" },
+        { "internalType": "bool",    "name": "initialized", "type": "bool" }
+      ],
+      "stateMutability": "view",
+      "type": "function"
+    }]"#
+);
+
+// ---- Minimal Algebra (QuickSwap v3) pool ABI: same curve, different names ----
+abigen!(
+    AlgebraPool,
+    r#"[{
+      "inputs": [],
+      "name": "globalState",
+      "outputs": [
+        { "internalType": "uint160", "name": "price", "type": "uint160" },
+        { "internalType": "int24",   "name": "tick",  "type": "int24"   },
+        { "internalType": "uint16",  "name": "fee",    "type": "uint16" },
+        { "internalType": "uint16",  "name": "timepointIndex", "type": "uint16" },
+        { "internalType": "uint8",   "name": "communityFeeToken0", "type": "uint8" },
+        { "internalType": "uint8",   "name": "communityFeeToken1", "type": "uint8" },
+        { "internalType": "bool",    "name": "unlocked", "type": "bool" }
+      ],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "liquidity",
+      "outputs": [{ "internalType": "uint128", "name": "", "type": "uint128" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "tickSpacing",
+      "outputs": [{ "internalType": "int24", "name": "", "type": "int24" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "token0",
+      "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "token1",
+      "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [{ "internalType": "int16", "name": "wordPosition", "type": "int16" }],
+      "name": "tickTable",
+      "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [{ "internalType": "int24", "name": "tick", "type": "int24" }],
+      "name": "ticks",
+      "outputs": [
+        { "internalType": "uint128", "name": "liquidityTotal", "type": "uint128" },
+        { "internalType": "int128",  "name": "liquidityDelta", "type": "int128" },
+        { "internalType": "uint256", "name": "outerFeeGrowth0Token", "type": "uint256" },
+        { "internalType": "uint256", "name": "outerFeeGrowth1Token", "type": "uint256" },
+        { "internalType": "int56",   "name": "outerTickCumulative", "type": "int56" },
+        { "internalType": "uint160", "name": "outerSecondsPerLiquidity", "type": "uint160" },
+        { "internalType": "uint32",  "name": "outerSecondsSpent", "type": "uint32" },
+        { "internalType": "bool",    "name": "initialized", "type": "bool" }
+      ],
+      "stateMutability": "view",
+      "type": "function"
+    }]"#
+);
+
+const Q96: u128 = 1u128 << 96;
+
+/// One initialized tick's liquidity delta, crossed when the price moves through it.
+#[derive(Clone, Copy, Debug)]
+pub struct TickInfo {
+    pub liquidity_net: i128,
+}
+
+/// Snapshot of a pool's state as of the last block we refreshed it at.
+#[derive(Clone, Debug)]
+pub struct PoolState {
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub fee: u32,
+    pub tick_spacing: i32,
+    /// Initialized ticks only, keyed by tick index, ordered for cheap walk.
+    pub ticks: BTreeMap<i32, TickInfo>,
+}
+
+impl PoolState {
+    /// Reads `slot0`, `liquidity`, `fee`, `tickSpacing`, and every initialized
+    /// tick (via the bitmap) for a Uniswap-v3 `pool`. Meant to be called once
+    /// per new block.
+    pub async fn fetch<M: Middleware + 'static>(pool: &UniswapV3Pool<M>) -> Result<Self> {
+        let slot0 = pool.slot_0().call().await.context("slot0")?;
+        let liquidity = pool.liquidity().call().await.context("liquidity")?;
+        let fee = pool.fee().call().await.context("fee")?;
+        let tick_spacing = pool.tick_spacing().call().await.context("tickSpacing")?;
+        let sqrt_price_x96 = slot0.0;
+        let tick = slot0.1;
+
+        let ticks = scan_initialized_ticks(
+            tick,
+            tick_spacing,
+            |word| {
+                let pool = pool.clone();
+                async move { Ok(pool.tick_bitmap(word as i16).call().await?) }
+            },
+            |tick_index| {
+                let pool = pool.clone();
+                async move { Ok(pool.ticks(tick_index).call().await?.1) }
+            },
+        )
+        .await?;
+
+        Ok(Self { sqrt_price_x96, tick, liquidity, fee, tick_spacing, ticks })
+    }
+
+    /// Same as [`PoolState::fetch`] but for an Algebra/QuickSwap pool, whose
+    /// price+tick live in `globalState` and whose bitmap is `tickTable`
+    /// instead of `tickBitmap`. The swap math afterwards is identical.
+    pub async fn fetch_algebra<M: Middleware + 'static>(pool: &AlgebraPool<M>) -> Result<Self> {
+        let state = pool.global_state().call().await.context("globalState")?;
+        let liquidity = pool.liquidity().call().await.context("liquidity")?;
+        let tick_spacing = pool.tick_spacing().call().await.context("tickSpacing")?;
+        let sqrt_price_x96 = state.0;
+        let tick = state.1;
+        let fee = state.2 as u32; // Algebra's fee (uint16) is dynamic but expressed in the same pips units
+
+        let ticks = scan_initialized_ticks(
+            tick,
+            tick_spacing,
+            |word| {
+                let pool = pool.clone();
+                async move { Ok(pool.tick_table(word as i16).call().await?) }
+            },
+            |tick_index| {
+                let pool = pool.clone();
+                async move { Ok(pool.ticks(tick_index).call().await?.1) }
+            },
+        )
+        .await?;
+
+        Ok(Self { sqrt_price_x96, tick, liquidity, fee, tick_spacing, ticks })
+    }
+}
+
+/// Shared bitmap-walk used by both [`PoolState::fetch`] and
+/// [`PoolState::fetch_algebra`]: scans the words either side of the current
+/// tick (initialized ticks are sparse, so this covers the range a realistic
+/// swap could cross) and reads `liquidityNet` for every set bit.
+async fn scan_initialized_ticks<BF, BFut, TF, TFut>(
+    tick: i32,
+    tick_spacing: i32,
+    mut bitmap_at: BF,
+    mut liquidity_net_at: TF,
+) -> Result<BTreeMap<i32, TickInfo>>
+where
+    BF: FnMut(i32) -> BFut,
+    BFut: Future<Output = Result<U256>>,
+    TF: FnMut(i32) -> TFut,
+    TFut: Future<Output = Result<i128>>,
+{
+    let mut ticks = BTreeMap::new();
+    let current_word = compress(tick, tick_spacing) >> 8;
+    for word in (current_word - 4)..=(current_word + 4) {
+        let bitmap = bitmap_at(word).await.context("tick bitmap")?;
+        if bitmap.is_zero() {
+            continue;
+        }
+        for bit in 0..256u32 {
+            if bitmap.bit(bit as usize) {
+                let compressed = (word << 8) + bit as i32;
+                let tick_index = compressed * tick_spacing;
+                let liquidity_net = liquidity_net_at(tick_index).await.context("tick liquidityNet")?;
+                ticks.insert(tick_index, TickInfo { liquidity_net });
+            }
+        }
+    }
+    Ok(ticks)
+}
+
+fn compress(tick: i32, tick_spacing: i32) -> i32 {
+    let mut c = tick / tick_spacing;
+    if tick % tick_spacing != 0 && tick < 0 {
+        c -= 1;
+    }
+    c
+}
+
+/// Simulates `quoteExactInputSingle` against a [`PoolState`] with no RPC
+/// calls. `zero_for_one` is true when swapping token0 for token1 (price
+/// falls), false for token1 -> token0 (price rises).
+pub fn quote_exact_input_single(pool: &PoolState, zero_for_one: bool, amount_in: U256) -> Result<U256> {
+    if amount_in.is_zero() {
+        return Ok(U256::zero());
+    }
+    if pool.liquidity == 0 {
+        bail!("pool has no liquidity");
+    }
+
+    let fee_pips = U256::from(pool.fee);
+    let amount_in_less_fee = amount_in * (U256::from(1_000_000u32) - fee_pips) / U256::from(1_000_000u32);
+
+    let mut sqrt_price = pool.sqrt_price_x96;
+    let mut liquidity = pool.liquidity;
+    let mut amount_remaining = amount_in_less_fee;
+    let mut amount_out = U256::zero();
+
+    let mut crossable: Vec<(i32, i128)> = pool
+        .ticks
+        .iter()
+        .filter(|(&t, _)| if zero_for_one { t < pool.tick } else { t > pool.tick })
+        .map(|(&t, info)| (t, info.liquidity_net))
+        .collect();
+    if zero_for_one {
+        crossable.sort_by_key(|&(t, _)| std::cmp::Reverse(t)); // descending: walk down from current tick
+    } else {
+        crossable.sort_by_key(|&(t, _)| t); // ascending: walk up from current tick
+    }
+
+    for (tick, liquidity_net) in crossable {
+        if amount_remaining.is_zero() {
+            break;
+        }
+        let sqrt_price_at_tick = sqrt_price_from_tick(tick)?;
+
+        let (step_in, step_out, sqrt_price_next) =
+            swap_step(sqrt_price, sqrt_price_at_tick, liquidity, amount_remaining, zero_for_one)?;
+
+        amount_remaining = amount_remaining.saturating_sub(step_in);
+        amount_out += step_out;
+        sqrt_price = sqrt_price_next;
+
+        if sqrt_price == sqrt_price_at_tick {
+            // Crossed the tick boundary exactly: apply its liquidityNet.
+            liquidity = if zero_for_one {
+                (liquidity as i128 - liquidity_net) as u128
+            } else {
+                (liquidity as i128 + liquidity_net) as u128
+            };
+        }
+    }
+
+    if !amount_remaining.is_zero() {
+        // Ran out of initialized ticks before consuming the full input: the
+        // remainder trades out against the last liquidity we had.
+        let target = if zero_for_one { U256::zero() } else { U256::MAX };
+        let (_, step_out, _) = swap_step(sqrt_price, target, liquidity, amount_remaining, zero_for_one)?;
+        amount_out += step_out;
+    }
+
+    Ok(amount_out)
+}
+
+/// One within-tick swap step: advances `sqrt_price` toward `sqrt_price_limit`
+/// by consuming up to `amount_in_max`, using `U512` intermediates for the
+/// `liquidity * sqrtPrice` products so they don't overflow `U256`. Returns
+/// `(amount_in_used, amount_out, sqrt_price_next)` — `amount_in_used` is less
+/// than `amount_in_max` whenever the step is cut short by `sqrt_price_limit`.
+fn swap_step(
+    sqrt_price: U256,
+    sqrt_price_limit: U256,
+    liquidity: u128,
+    amount_in_max: U256,
+    zero_for_one: bool,
+) -> Result<(U256, U256, U256)> {
+    let l = U512::from(liquidity);
+    let q96 = U512::from(Q96);
+
+    // Price if the *entire* amount_in_max were consumed, ignoring the limit.
+    let sqrt_price_next_unclamped = if zero_for_one {
+        // sqrtP_next = L*sqrtP*2^96 / (L*2^96 + amountIn*sqrtP)
+        let numerator = l * U512::from(sqrt_price) * q96;
+        let denominator = l * q96 + U512::from(amount_in_max) * U512::from(sqrt_price);
+        narrow(numerator / denominator)?
+    } else {
+        // sqrtP_next = sqrtP + amountIn*2^96 / L
+        let delta = U512::from(amount_in_max) * q96 / l;
+        sqrt_price.checked_add(narrow(delta)?).ok_or_else(|| anyhow!("sqrt price overflow"))?
+    };
+
+    let limit_reached = if zero_for_one {
+        sqrt_price_next_unclamped <= sqrt_price_limit
+    } else {
+        sqrt_price_next_unclamped >= sqrt_price_limit
+    };
+    let sqrt_price_next = if limit_reached { sqrt_price_limit } else { sqrt_price_next_unclamped };
+
+    let amount_in_used = if limit_reached {
+        amount_delta_in(sqrt_price, sqrt_price_next, l, q96, zero_for_one)?
+    } else {
+        amount_in_max
+    };
+
+    let amount_out = if zero_for_one {
+        // delta amount1 = L*(sqrtP - sqrtP_next) / 2^96
+        let delta = l * (U512::from(sqrt_price) - U512::from(sqrt_price_next)) / q96;
+        narrow(delta)?
+    } else {
+        // delta amount0 = L*(sqrtP_next - sqrtP)*2^96 / (sqrtP*sqrtP_next)
+        let numerator = l * (U512::from(sqrt_price_next) - U512::from(sqrt_price)) * q96;
+        let denominator = U512::from(sqrt_price) * U512::from(sqrt_price_next);
+        narrow(numerator / denominator)?
+    };
+
+    Ok((amount_in_used, amount_out, sqrt_price_next))
+}
+
+/// The input token actually consumed moving from `sqrt_price` to
+/// `sqrt_price_next` (the inverse of `swap_step`'s output-side deltas).
+fn amount_delta_in(sqrt_price: U256, sqrt_price_next: U256, l: U512, q96: U512, zero_for_one: bool) -> Result<U256> {
+    if zero_for_one {
+        // amount0 = L*(sqrtP - sqrtPnext)*2^96 / (sqrtPnext*sqrtP)
+        let numerator = l * (U512::from(sqrt_price) - U512::from(sqrt_price_next)) * q96;
+        let denominator = U512::from(sqrt_price_next) * U512::from(sqrt_price);
+        narrow(numerator / denominator)
+    } else {
+        // amount1 = L*(sqrtPnext - sqrtP) / 2^96
+        let delta = l * (U512::from(sqrt_price_next) - U512::from(sqrt_price)) / q96;
+        narrow(delta)
+    }
+}
+
+fn narrow(x: U512) -> Result<U256> {
+    if x > U512::from(U256::MAX) {
+        bail!("U512 value does not fit in U256");
+    }
+    let mut buf = [0u8; 64];
+    x.to_big_endian(&mut buf);
+    Ok(U256::from_big_endian(&buf[32..]))
+}
+
+/// Largest tick magnitude representable by the Q64.96 sqrt-price format.
+const MAX_TICK: u32 = 887272;
+
+/// Exact integer `sqrtPriceX96` at a given tick, ported from Uniswap v3's
+/// `TickMath.getSqrtRatioAtTick` bit-shift table (no floating point).
+fn sqrt_price_from_tick(tick: i32) -> Result<U256> {
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK {
+        bail!("tick {tick} exceeds MAX_TICK");
+    }
+
+    let mut ratio: U256 = if abs_tick & 0x1 != 0 {
+        hex_const("fffcb933bd6fad37aa2d162d1a594001")
+    } else {
+        U256::one() << 128
+    };
+
+    const STEPS: [(u32, &str); 19] = [
+        (0x2, "fff97272373d413259a46990580e213a"),
+        (0x4, "fff2e50f5f656932ef12357cf3c7fdcc"),
+        (0x8, "ffe5caca7e10e4e61c3624eaa0941cd0"),
+        (0x10, "ffcb9843d60f6159c9db58835c926644"),
+        (0x20, "ff973b41fa98c081472e6896dfb254c0"),
+        (0x40, "ff2ea16466c96a3843ec78b326b52861"),
+        (0x80, "fe5dee046a99a2a811c461f1969c3053"),
+        (0x100, "fcbe86c7900a88aedcffc83b479aa3a4"),
+        (0x200, "f987a7253ac413176f2b074cf7815e54"),
+        (0x400, "f3392b0822b70005940c7a398e4b70f3"),
+        (0x800, "e7159475a2c29b7443b29c7fa6e889d9"),
+        (0x1000, "d097f3bdfd2022b8845ad8f792aa5825"),
+        (0x2000, "a9f746462d870fdf8a65dc1f90e061e5"),
+        (0x4000, "70d869a156d2a1b890bb3df62baf32f7"),
+        (0x8000, "31be135f97d08fd981231505542fcfa6"),
+        (0x10000, "9aa508b5b7a84e1c677de54f3e99bc9"),
+        (0x20000, "5d6af8dedb81196699c329225ee604"),
+        (0x40000, "2216e584f5fa1ea926041bedfe98"),
+        (0x80000, "48a170391f7dc42444e8fa2"),
+    ];
+
+    for (bit, hex) in STEPS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * hex_const(hex)) >> 128;
+        }
+    }
+
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Divide by 1<<32 rounding up to go from a Q128.128 ratio to a Q128.96 sqrt price.
+    let shifted = ratio >> 32;
+    let remainder = ratio & ((U256::one() << 32) - U256::one());
+    Ok(if remainder.is_zero() { shifted } else { shifted + U256::one() })
+}
+
+fn hex_const(hex: &str) -> U256 {
+    U256::from_str_radix(hex, 16).expect("valid hex constant")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_price_from_tick_zero_is_q96() {
+        // Tick 0 is price 1.0, whose Q64.96 sqrt price is exactly 2^96.
+        assert_eq!(sqrt_price_from_tick(0).unwrap(), U256::one() << 96);
+    }
+
+    #[test]
+    fn sqrt_price_from_tick_rejects_out_of_range() {
+        assert!(sqrt_price_from_tick((MAX_TICK + 1) as i32).is_err());
+        assert!(sqrt_price_from_tick(-((MAX_TICK + 1) as i32)).is_err());
+    }
+
+    #[test]
+    fn sqrt_price_from_tick_matches_uniswap_tick_math_reference_vectors() {
+        // Standard Uniswap v3 TickMath.getSqrtRatioAtTick reference values -
+        // these exercise the bit-shift table, not just the tick-0 trivial case.
+        assert_eq!(sqrt_price_from_tick(1).unwrap(), U256::from_dec_str("79232123823359799118286999568").unwrap());
+        assert_eq!(sqrt_price_from_tick(-887272).unwrap(), U256::from(4295128739u64));
+    }
+
+    #[test]
+    fn swap_step_zero_amount_in_is_a_no_op() {
+        let sqrt_price = U256::one() << 96;
+        let (in_used, out, next) = swap_step(sqrt_price, U256::zero(), 1_000_000, U256::zero(), true).unwrap();
+        assert_eq!((in_used, out, next), (U256::zero(), U256::zero(), sqrt_price));
+
+        let (in_used, out, next) = swap_step(sqrt_price, U256::MAX, 1_000_000, U256::zero(), false).unwrap();
+        assert_eq!((in_used, out, next), (U256::zero(), U256::zero(), sqrt_price));
+    }
+
+    #[test]
+    fn swap_step_token1_in_matches_hand_computed_whitepaper_formula() {
+        // sqrtP=2^96 (price 1), L=1_000_000, amountIn=1_000_000 of token1 (zero_for_one=false).
+        // sqrtPnext = sqrtP + amountIn*2^96/L = 2^96 + 2^96 = 2^97 (Uniswap v3 whitepaper 6.8).
+        // amountOut (token0) = L*(sqrtPnext-sqrtP)*2^96/(sqrtP*sqrtPnext) = 1_000_000/2 = 500_000.
+        let sqrt_price = U256::one() << 96;
+        let (in_used, out, next) = swap_step(sqrt_price, U256::MAX, 1_000_000, U256::from(1_000_000u64), false).unwrap();
+        assert_eq!(in_used, U256::from(1_000_000u64));
+        assert_eq!(next, U256::one() << 97);
+        assert_eq!(out, U256::from(500_000u64));
+    }
+
+    #[test]
+    fn swap_step_token0_in_matches_hand_computed_whitepaper_formula() {
+        // Inverse of the above: sqrtP=2^97, L=1_000_000, amountIn=500_000 of token0
+        // (zero_for_one=true) should land exactly back at sqrtP=2^96 and output the
+        // 1_000_000 token1 that was put in above.
+        let sqrt_price = U256::one() << 97;
+        let (in_used, out, next) = swap_step(sqrt_price, U256::zero(), 1_000_000, U256::from(500_000u64), true).unwrap();
+        assert_eq!(in_used, U256::from(500_000u64));
+        assert_eq!(next, U256::one() << 96);
+        assert_eq!(out, U256::from(1_000_000u64));
+    }
+}
@@ -0,0 +1,113 @@
+//! Structured, newline-delimited JSON logging for detected arbitrage.
+//!
+//! `profit.txt` used to get a human-formatted `format!` line that nothing
+//! downstream could parse. `ArbRecord` is the machine-readable replacement:
+//! one JSON object per line, with `U256` amounts serialized through
+//! [`HexOrDecimalU256`] so the log interoperates with other Ethereum tooling
+//! that expects either `0x`-prefixed hex or a decimal string.
+
+use anyhow::{Context, Result};
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// `serde_with` adapter that writes a `U256` as `0x`-prefixed hex and accepts
+/// either hex or a plain decimal string on the way back in.
+pub struct HexOrDecimalU256;
+
+impl SerializeAs<U256> for HexOrDecimalU256 {
+    fn serialize_as<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("{value:#x}"))
+    }
+}
+
+impl<'de> DeserializeAs<'de, U256> for HexOrDecimalU256 {
+    fn deserialize_as<D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if let Some(hex) = s.strip_prefix("0x") {
+            U256::from_str_radix(hex, 16).map_err(serde::de::Error::custom)
+        } else {
+            U256::from_dec_str(&s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// One detected-arbitrage opportunity, serialized as a single JSONL line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbRecord {
+    pub timestamp_unix: u64,
+    pub block_number: u64,
+    pub chain_id: u64,
+    pub path_label: String,
+    #[serde(with = "serde_with::As::<HexOrDecimalU256>")]
+    pub start_usdc: U256,
+    #[serde(with = "serde_with::As::<HexOrDecimalU256>")]
+    pub weth_bought: U256,
+    #[serde(with = "serde_with::As::<HexOrDecimalU256>")]
+    pub usdc_back: U256,
+    #[serde(with = "serde_with::As::<HexOrDecimalU256>")]
+    pub gas_usdc: U256,
+    /// Net profit as a formatted decimal USDC string (e.g. `"-1.23"`), via
+    /// `SignedUsdc`'s `Display` impl. Unlike the sibling fields above, this
+    /// is not base units through [`HexOrDecimalU256`]: `SignedUsdc` wraps a
+    /// signed `I256`, which that adapter doesn't support.
+    pub net_usdc: String,
+}
+
+/// Appends `record` as one JSON line to `path`, creating it if needed.
+pub fn append_record(path: impl AsRef<Path>, record: &ArbRecord) -> Result<()> {
+    let line = serde_json::to_string(record).context("serializing ArbRecord")?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path).context("opening arb log")?;
+    writeln!(file, "{line}").context("writing arb log line")
+}
+
+/// `--replay <file>` parsed from `argv`, if present.
+pub fn requested_replay_path(args: &[String]) -> Option<&str> {
+    let pos = args.iter().position(|a| a == "--replay")?;
+    args.get(pos + 1).map(String::as_str)
+}
+
+/// Reads back every `ArbRecord` previously appended to `path`, in order.
+pub fn read_records(path: impl AsRef<Path>) -> Result<Vec<ArbRecord>> {
+    let file = OpenOptions::new().read(true).open(path).context("opening arb log")?;
+    BufReader::new(file)
+        .lines()
+        .filter(|l| l.as_ref().map(|s| !s.trim().is_empty()).unwrap_or(true))
+        .map(|line| {
+            let line = line.context("reading arb log line")?;
+            serde_json::from_str(&line).context("parsing ArbRecord")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "serde_with::As::<HexOrDecimalU256>")] U256);
+
+    #[test]
+    fn hex_or_decimal_u256_serializes_as_hex() {
+        let value = Wrapper(U256::from(291u64)); // 0x123
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""0x123""#);
+    }
+
+    #[test]
+    fn hex_or_decimal_u256_deserializes_either_encoding_to_the_same_value() {
+        let expected = U256::from(291u64);
+        let from_hex: Wrapper = serde_json::from_str(r#""0x123""#).unwrap();
+        let from_decimal: Wrapper = serde_json::from_str(r#""291""#).unwrap();
+        assert_eq!(from_hex.0, expected);
+        assert_eq!(from_decimal.0, expected);
+    }
+}
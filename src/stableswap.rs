@@ -0,0 +1,190 @@
+//! Local StableSwap (Curve-style) quoting for stablecoin pools.
+//!
+//! [`pool_sim`] only models the constant-product/concentrated-liquidity curve
+//! used by Uniswap v3 and Algebra, so it misses the large USDC/USDT/DAI
+//! stable-pool arbitrage space. This module reproduces the StableSwap
+//! invariant in pure integer math: solve for `D` via Newton iteration, then
+//! quote an exact-in swap by holding `D` fixed and solving a second Newton
+//! loop for the new output balance.
+
+use anyhow::{bail, Result};
+use ethers::prelude::*;
+
+abigen!(
+    CurvePool,
+    r#"[{
+      "inputs": [],
+      "name": "A",
+      "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [{ "internalType": "uint256", "name": "i", "type": "uint256" }],
+      "name": "balances",
+      "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+      "stateMutability": "view",
+      "type": "function"
+    }, {
+      "inputs": [],
+      "name": "fee",
+      "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+      "stateMutability": "view",
+      "type": "function"
+    }]"#
+);
+
+/// Curve fees are expressed in 1e10 units (`FEE_DENOMINATOR`).
+const FEE_DENOMINATOR: u64 = 10_000_000_000;
+
+/// Snapshot of a stable pool's state as of the last block we refreshed it at.
+#[derive(Clone, Debug)]
+pub struct StablePoolState {
+    /// Coin balances, index-aligned with the pool's `coins`/`balances`.
+    pub balances: Vec<U256>,
+    /// Amplification coefficient.
+    pub amplification: U256,
+    /// Swap fee, in `FEE_DENOMINATOR` units.
+    pub fee: U256,
+}
+
+impl StablePoolState {
+    /// Reads `A`, `fee`, and every coin's balance for `pool`. Meant to be
+    /// called once per new block, same cadence as [`pool_sim::PoolState`].
+    pub async fn fetch<M: Middleware + 'static>(pool: &CurvePool<M>, n_coins: usize) -> Result<Self> {
+        let amplification = pool.a().call().await?;
+        let fee = pool.fee().call().await?;
+        let mut balances = Vec::with_capacity(n_coins);
+        for i in 0..n_coins {
+            balances.push(pool.balances(U256::from(i)).call().await?);
+        }
+        Ok(Self { balances, amplification, fee })
+    }
+}
+
+/// Solves for the StableSwap invariant `D` given balances `x_0..x_{n-1}` and
+/// amplification `amp`, by Newton iteration on
+/// `f(D) = A*n^n*sum(x_i) + D - A*D*n^n - D^(n+1)/(n^n*prod(x_i))`.
+pub fn compute_d(balances: &[U256], amp: U256) -> Result<U256> {
+    let n = U256::from(balances.len());
+    let sum: U256 = balances.iter().fold(U256::zero(), |acc, b| acc + *b);
+    if sum.is_zero() {
+        return Ok(U256::zero());
+    }
+
+    let ann = amp * n.pow(n); // A * n^n
+    let mut d = sum;
+
+    for _ in 0..255 {
+        // d_p = D^(n+1) / (n^n * prod(x_i))
+        let mut d_p = d;
+        for b in balances {
+            d_p = d_p * d / (b * n);
+        }
+        let d_prev = d;
+        let numerator = (ann * sum + d_p * n) * d;
+        let denominator = (ann - U256::one()) * d + (n + U256::one()) * d_p;
+        if denominator.is_zero() {
+            bail!("StableSwap D iteration hit a zero denominator");
+        }
+        d = numerator / denominator;
+
+        let diff = if d > d_prev { d - d_prev } else { d_prev - d };
+        if diff <= U256::one() {
+            return Ok(d);
+        }
+    }
+    bail!("StableSwap D did not converge")
+}
+
+/// Solves for the new balance of coin `j` given every other balance held
+/// fixed and `D` held fixed, by Newton iteration on the per-coin quadratic
+/// `y_{k+1} = (y_k^2 + c) / (2*y_k + b - D)`.
+fn compute_y(balances: &[U256], amp: U256, d: U256, j: usize) -> Result<U256> {
+    let n = U256::from(balances.len());
+    let ann = amp * n.pow(n);
+
+    let mut c = d;
+    let mut s = U256::zero();
+    for (i, b) in balances.iter().enumerate() {
+        if i == j {
+            continue;
+        }
+        s += *b;
+        c = c * d / (*b * n);
+    }
+    c = c * d / (ann * n);
+    let b_coef = s + d / ann;
+
+    let mut y = d;
+    for _ in 0..255 {
+        let y_prev = y;
+        let numerator = y * y + c;
+        let denominator = U256::from(2) * y + b_coef;
+        if denominator <= d {
+            bail!("StableSwap y iteration underflowed (2y + b <= D)");
+        }
+        y = numerator / (denominator - d);
+
+        let diff = if y > y_prev { y - y_prev } else { y_prev - y };
+        if diff <= U256::one() {
+            return Ok(y);
+        }
+    }
+    bail!("StableSwap y did not converge")
+}
+
+/// Quotes an exact-in swap of `amount_in` from coin `i` to coin `j`.
+pub fn quote_exact_input(pool: &StablePoolState, i: usize, j: usize, amount_in: U256) -> Result<U256> {
+    if i == j || i >= pool.balances.len() || j >= pool.balances.len() {
+        bail!("invalid coin indices {i}/{j} for a {}-coin pool", pool.balances.len());
+    }
+
+    let d = compute_d(&pool.balances, pool.amplification)?;
+
+    let amount_in_less_fee = amount_in * (U256::from(FEE_DENOMINATOR) - pool.fee) / U256::from(FEE_DENOMINATOR);
+
+    let mut balances_after_in = pool.balances.clone();
+    balances_after_in[i] += amount_in_less_fee;
+
+    let y = compute_y(&balances_after_in, pool.amplification, d, j)?;
+    let x_j_old = pool.balances[j];
+    if y + U256::one() > x_j_old {
+        bail!("StableSwap quote produced a non-positive output");
+    }
+    Ok(x_j_old - y - U256::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_d_of_balanced_pool_is_the_sum() {
+        // When every balance is equal, D = n*x satisfies the invariant exactly,
+        // so Newton iteration should converge to precisely the sum.
+        let balances = vec![U256::from(1_000_000u64), U256::from(1_000_000u64), U256::from(1_000_000u64)];
+        let d = compute_d(&balances, U256::from(100u64)).unwrap();
+        assert_eq!(d, U256::from(3_000_000u64));
+    }
+
+    #[test]
+    fn quote_exact_input_on_balanced_pool_is_near_one_to_one() {
+        // A small trade against a large, balanced, zero-fee pool should come
+        // back close to 1:1 - the StableSwap curve is flat near par.
+        let pool = StablePoolState {
+            balances: vec![U256::from(1_000_000_000u64), U256::from(1_000_000_000u64)],
+            amplification: U256::from(100u64),
+            fee: U256::zero(),
+        };
+        let amount_in = U256::from(1_000u64);
+        let amount_out = quote_exact_input(&pool, 0, 1, amount_in).unwrap();
+        assert!(amount_out <= amount_in);
+        assert!(amount_in - amount_out <= U256::from(2u64));
+    }
+
+    #[test]
+    fn quote_exact_input_rejects_same_coin() {
+        let pool = StablePoolState { balances: vec![U256::from(1u64), U256::from(1u64)], amplification: U256::from(100u64), fee: U256::zero() };
+        assert!(quote_exact_input(&pool, 0, 0, U256::from(1u64)).is_err());
+    }
+}